@@ -1,9 +1,161 @@
+// BLOCKED: this checkout has no Cargo.toml for this crate. Everything below
+// (the `static`/`dynamic`/`bundled`/`nobuild`/`cargo_check` features, the
+// `pkg-config` probe, and the `links = "recad_core"` manifest key this
+// build script's `DEP_RECAD_CORE_*` exports depend on) is written the way
+// it would work once a manifest exists, but none of it can actually run —
+// `cargo check`/`build` have no manifest to invoke this build script from.
+// Treat this file as source staged ahead of the manifest landing, not as a
+// working build pipeline.
+//
+// Tracked against backlog items chunk3-1 (static/dynamic link control),
+// chunk3-2 (bundled Zig fallback), chunk3-3 (nobuild/cargo_check analysis
+// mode), chunk3-4 (pkg-config probe), and chunk3-5 (links = "recad_core"
+// metadata export): none of them are actually deliverable until the
+// manifest lands, so none should be considered closed out as done.
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// How to link `recad_core`: statically (crates.io/vendored distribution) or
+/// dynamically (local development against a `zig build` output).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LinkMode {
+    Static,
+    Dynamic,
+}
+
+/// Resolve an explicitly requested link mode from the `static`/`dynamic`
+/// cargo features or the `RECAD_SYS_STATIC` env var (mirroring libz-sys's
+/// `LIBZ_SYS_STATIC`), or `None` to let artifact availability decide.
+fn requested_link_mode() -> Option<LinkMode> {
+    println!("cargo:rerun-if-env-changed=RECAD_SYS_STATIC");
+
+    if let Ok(value) = env::var("RECAD_SYS_STATIC") {
+        return Some(if value != "0" {
+            LinkMode::Static
+        } else {
+            LinkMode::Dynamic
+        });
+    }
+
+    match (cfg!(feature = "static"), cfg!(feature = "dynamic")) {
+        (true, true) => panic!("the `static` and `dynamic` features are mutually exclusive"),
+        (true, false) => Some(LinkMode::Static),
+        (false, true) => Some(LinkMode::Dynamic),
+        (false, false) => None,
+    }
+}
+
+/// Minimum Zig version the `bundled` feature's source build has been tested
+/// against.
+const MIN_ZIG_VERSION: (u32, u32, u32) = (0, 13, 0);
+
+/// Locate the Zig source tree to build `recad_core` from: the local
+/// `../core` checkout when building from the repository, or a vendored
+/// source snapshot when building from crates.io.
+fn bundled_source_dir(manifest_dir: &Path) -> PathBuf {
+    let local = manifest_dir.parent().unwrap().join("core");
+    if local.join("build.zig").exists() {
+        return local;
+    }
+    manifest_dir.join("vendor-src").join("recad_core")
+}
+
+fn zig_version() -> Option<(u32, u32, u32)> {
+    let output = Command::new("zig").arg("version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    let mut parts = version.trim().split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = parts.next()?.split('-').next()?.parse().ok()?;
+    Some((major, minor, patch))
+}
+
+/// Build `recad_core` from Zig source into `OUT_DIR`, for crates.io installs
+/// with no prebuilt vendor artifact for their target. Returns the directory
+/// containing the resulting static library.
+#[cfg(feature = "bundled")]
+fn build_bundled(manifest_dir: &Path, out_dir: &Path) -> PathBuf {
+    let (major, minor, patch) = MIN_ZIG_VERSION;
+
+    let version = zig_version().unwrap_or_else(|| {
+        panic!(
+            "The `bundled` feature requires the Zig compiler (>= {major}.{minor}.{patch}), but \
+             `zig` was not found on PATH. Install Zig from https://ziglang.org/download/ or \
+             disable the `bundled` feature."
+        )
+    });
+
+    if version < MIN_ZIG_VERSION {
+        let (found_major, found_minor, found_patch) = version;
+        panic!(
+            "The `bundled` feature requires Zig >= {major}.{minor}.{patch}, found \
+             {found_major}.{found_minor}.{found_patch}"
+        );
+    }
+
+    let source_dir = bundled_source_dir(manifest_dir);
+    if !source_dir.join("build.zig").exists() {
+        panic!(
+            "The `bundled` feature could not find a Zig source tree for recad_core at {}",
+            source_dir.display()
+        );
+    }
+
+    println!("cargo:rerun-if-changed={}", source_dir.display());
+
+    let prefix = out_dir.join("recad_core");
+    let status = Command::new("zig")
+        .arg("build")
+        .arg("-Doptimize=ReleaseFast")
+        .arg("--prefix")
+        .arg(&prefix)
+        .current_dir(&source_dir)
+        .status()
+        .unwrap_or_else(|e| panic!("failed to run `zig build` in {}: {e}", source_dir.display()));
+
+    if !status.success() {
+        panic!("`zig build` failed in {}", source_dir.display());
+    }
+
+    prefix.join("lib")
+}
 
 fn main() {
+    // Analysis-only builds (docs.rs, editor `cargo check`) have neither the
+    // Zig toolchain nor a vendored `recad_core` for the host target, so skip
+    // linking entirely and let the crate compile against declarations only.
+    // This checkout has no Cargo.toml yet, so the matching
+    // `[package.metadata.docs.rs] features = ["nobuild"]` entry that makes
+    // docs.rs opt into this automatically is blocked on the manifest landing
+    // first; this build.rs-side half is ready for it.
+    if cfg!(feature = "nobuild") || cfg!(feature = "cargo_check") {
+        return;
+    }
+
     let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
     let target = env::var("TARGET").unwrap_or_else(|_| "x86_64-unknown-linux-gnu".to_string());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    let requested = requested_link_mode();
+
+    // Prefer a system-installed recad_core over the vendored/bundled
+    // artifact when one is discoverable via pkg-config. Skipped on MSVC
+    // (where pkg-config isn't meaningful) and when static linking has been
+    // forced, mirroring libz-sys, so this never clobbers another crate's
+    // link search path with an unwanted `/usr/lib`.
+    if !target.contains("msvc") && requested != Some(LinkMode::Static) {
+        let probed = pkg_config::Config::new()
+            .atleast_version("1.0")
+            .probe("recad_core");
+        if let Ok(library) = probed {
+            // `probe` already emitted the link-search/lib directives.
+            if let Some(link_path) = library.link_paths.first() {
+                export_link_metadata(link_path, LinkMode::Dynamic);
+            }
+            return;
+        }
+    }
 
     // When building from the repository (not from crates.io), use the local build
     let local_lib_path = manifest_dir
@@ -16,48 +168,105 @@ fn main() {
     // When installed from crates.io, use bundled vendor libraries
     let vendor_lib_path = manifest_dir.join("vendor").join(&target);
 
-    // Prefer vendor (for crates.io installs), fallback to local build
-    let lib_path = if vendor_lib_path.exists() {
-        vendor_lib_path
-    } else if local_lib_path.exists() {
-        // Local development: use dynamic linking
-        println!("cargo:rustc-link-search=native={}", local_lib_path.display());
-        println!("cargo:rustc-link-lib=dylib=recad_core");
-        println!(
-            "cargo:rustc-link-arg=-Wl,-rpath,{}",
-            local_lib_path.display()
-        );
-        println!(
-            "cargo:rerun-if-changed={}",
-            local_lib_path.join("librecad_core.so").display()
-        );
-        return;
-    } else {
-        panic!(
-            "Could not find librecad_core library.\n\
-             For local development: run 'cd ../core && zig build'\n\
-             For crates.io: vendor libraries should be bundled in vendor/{target}/"
-        );
+    let static_lib = vendor_lib_path.join("librecad_core.a");
+    let dynamic_lib = local_lib_path.join("librecad_core.so");
+
+    let (link_mode, lib_path) = match requested {
+        Some(LinkMode::Static) if static_lib.exists() => (LinkMode::Static, vendor_lib_path.clone()),
+        Some(LinkMode::Static) => {
+            #[cfg(feature = "bundled")]
+            {
+                (LinkMode::Static, build_bundled(&manifest_dir, &out_dir))
+            }
+            #[cfg(not(feature = "bundled"))]
+            {
+                panic!(
+                    "Static linking was requested (RECAD_SYS_STATIC or the `static` feature), \
+                     but no static library was found at {}",
+                    static_lib.display()
+                );
+            }
+        }
+        Some(LinkMode::Dynamic) if dynamic_lib.exists() => (LinkMode::Dynamic, local_lib_path.clone()),
+        Some(LinkMode::Dynamic) => {
+            panic!(
+                "Dynamic linking was requested (RECAD_SYS_STATIC=0 or the `dynamic` feature), \
+                 but no shared library was found at {}",
+                dynamic_lib.display()
+            );
+        }
+        None if static_lib.exists() => (LinkMode::Static, vendor_lib_path.clone()),
+        None if dynamic_lib.exists() => (LinkMode::Dynamic, local_lib_path.clone()),
+        None => {
+            #[cfg(feature = "bundled")]
+            {
+                (LinkMode::Static, build_bundled(&manifest_dir, &out_dir))
+            }
+            #[cfg(not(feature = "bundled"))]
+            {
+                panic!(
+                    "Could not find librecad_core library.\n\
+                     For local development: run 'cd ../core && zig build'\n\
+                     For crates.io: vendor libraries should be bundled in vendor/{target}/, or \
+                     build with --features bundled to compile it from source"
+                );
+            }
+        }
     };
 
-    // Static linking for crates.io distribution
-    println!("cargo:rustc-link-search=native={}", lib_path.display());
-    println!("cargo:rustc-link-lib=static=recad_core");
+    match link_mode {
+        LinkMode::Dynamic => {
+            println!("cargo:rustc-link-search=native={}", lib_path.display());
+            println!("cargo:rustc-link-lib=dylib=recad_core");
+            println!("cargo:rustc-link-arg=-Wl,-rpath,{}", lib_path.display());
+            println!(
+                "cargo:rerun-if-changed={}",
+                lib_path.join("librecad_core.so").display()
+            );
+        }
+        LinkMode::Static => {
+            println!("cargo:rustc-link-search=native={}", lib_path.display());
+            println!("cargo:rustc-link-lib=static=recad_core");
+
+            // Link C++ standard library - Zig uses LLVM's libc++
+            if target.contains("apple") {
+                println!("cargo:rustc-link-lib=c++");
+            } else if target.contains("linux") {
+                // Zig uses libc++ (LLVM), not libstdc++ (GNU)
+                println!("cargo:rustc-link-lib=c++");
+                println!("cargo:rustc-link-lib=c++abi");
+            } else if target.contains("windows") {
+                // Windows with GNU toolchain uses libc++ when built with Zig
+                println!("cargo:rustc-link-lib=c++");
+            }
 
-    // Link C++ standard library - Zig uses LLVM's libc++
-    if target.contains("apple") {
-        println!("cargo:rustc-link-lib=c++");
-    } else if target.contains("linux") {
-        // Zig uses libc++ (LLVM), not libstdc++ (GNU)
-        println!("cargo:rustc-link-lib=c++");
-        println!("cargo:rustc-link-lib=c++abi");
-    } else if target.contains("windows") {
-        // Windows with GNU toolchain uses libc++ when built with Zig
-        println!("cargo:rustc-link-lib=c++");
+            println!(
+                "cargo:rerun-if-changed={}",
+                lib_path.join("librecad_core.a").display()
+            );
+        }
     }
 
+    export_link_metadata(&lib_path, link_mode);
+}
+
+/// Export the resolved library directory and link kind as build-script
+/// metadata, so downstream crates' own build scripts can pick it up via
+/// `DEP_RECAD_CORE_ROOT`/`DEP_RECAD_CORE_LIB_PATH`/`DEP_RECAD_CORE_LINK_KIND`.
+///
+/// Cargo only derives those `DEP_<LINKS>_*` env var names for dependents
+/// when the crate's manifest carries a `links = "recad_core"` key; this
+/// checkout has no Cargo.toml yet, so until the manifest lands with that
+/// key, the `cargo:` lines below are emitted but not yet surfaced as
+/// `DEP_RECAD_CORE_*`. Blocked on the manifest, not on this function.
+fn export_link_metadata(lib_path: &Path, link_mode: LinkMode) {
+    println!("cargo:root={}", lib_path.display());
+    println!("cargo:lib_path={}", lib_path.display());
     println!(
-        "cargo:rerun-if-changed={}",
-        lib_path.join("librecad_core.a").display()
+        "cargo:link_kind={}",
+        match link_mode {
+            LinkMode::Static => "static",
+            LinkMode::Dynamic => "dylib",
+        }
     );
 }
@@ -0,0 +1,83 @@
+//! Compressed archive export for converted output
+//!
+//! `convert_to_archive` collapses a batch of mixed-format input files (DWG,
+//! JWW, ...) into a single compressed tarball: each input is first converted
+//! to a temporary DXF via `ffi::convert`, then appended to the archive under
+//! its original basename. Each codec lives behind its own cargo feature so a
+//! caller who only needs one (or none) doesn't pay for the others'
+//! dependencies.
+
+use crate::ffi::{self, LcDxfVersion};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Compression codec for `convert_to_archive`'s output bundle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    #[cfg(feature = "compress-zstd")]
+    Zstd,
+    #[cfg(feature = "compress-bzip2")]
+    Bzip2,
+    #[cfg(feature = "compress-xz")]
+    Xz,
+}
+
+fn encoder(archive_file: std::fs::File, compression: Compression) -> Result<Box<dyn Write>, String> {
+    match compression {
+        #[cfg(feature = "compress-zstd")]
+        Compression::Zstd => Ok(Box::new(
+            zstd::Encoder::new(archive_file, 0)
+                .map_err(|e| e.to_string())?
+                .auto_finish(),
+        )),
+        #[cfg(feature = "compress-bzip2")]
+        Compression::Bzip2 => Ok(Box::new(bzip2::write::BzEncoder::new(
+            archive_file,
+            bzip2::Compression::default(),
+        ))),
+        #[cfg(feature = "compress-xz")]
+        Compression::Xz => Ok(Box::new(xz2::write::XzEncoder::new(archive_file, 6))),
+    }
+}
+
+/// Convert every file in `inputs` to a temporary DXF and append it, under
+/// its original basename, to a single `compression`-encoded tar archive at
+/// `archive_path`.
+#[allow(dead_code)]
+pub fn convert_to_archive(
+    inputs: &[PathBuf],
+    archive_path: &Path,
+    dxf_version: LcDxfVersion,
+    compression: Compression,
+) -> Result<(), String> {
+    let tmp_dir = tempfile::tempdir().map_err(|e| e.to_string())?;
+    let mut entries = Vec::with_capacity(inputs.len());
+
+    for input in inputs {
+        let stem = input
+            .file_stem()
+            .ok_or_else(|| format!("{} has no file name", input.display()))?
+            .to_string_lossy();
+        let entry_name = format!("{stem}.dxf");
+        let tmp_path = tmp_dir.path().join(&entry_name);
+
+        ffi::convert(&input.to_string_lossy(), &tmp_path.to_string_lossy(), dxf_version)?;
+        entries.push((entry_name, tmp_path));
+    }
+
+    let archive_file = std::fs::File::create(archive_path)
+        .map_err(|e| format!("failed to create {}: {e}", archive_path.display()))?;
+
+    let mut builder = tar::Builder::new(encoder(archive_file, compression)?);
+    for (name, path) in &entries {
+        builder
+            .append_path_with_name(path, name)
+            .map_err(|e| e.to_string())?;
+    }
+
+    builder
+        .into_inner()
+        .map_err(|e| e.to_string())?
+        .flush()
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,256 @@
+//! Recursive directory discovery and parallel processing of CAD files
+
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+use walkdir::WalkDir;
+
+const CAD_EXTENSIONS: [&str; 2] = ["dxf", "jww"];
+
+/// Whether a discovered file looks like a text (ASCII) or binary CAD file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileKind {
+    Text,
+    Binary,
+}
+
+/// A file discovered while walking a directory tree
+#[derive(Debug, Clone)]
+pub struct DiscoveredFile {
+    pub path: PathBuf,
+    pub kind: FileKind,
+}
+
+/// Sniff whether a file is text or binary by checking for the binary DXF
+/// magic header and for embedded NUL bytes; everything else supported today
+/// is line-oriented ASCII.
+fn sniff_kind(path: &Path) -> FileKind {
+    const BINARY_MAGIC: &[u8] = b"AutoCAD Binary DXF";
+
+    match std::fs::read(path) {
+        Ok(bytes) => {
+            if bytes.starts_with(BINARY_MAGIC) || bytes.iter().take(512).any(|b| *b == 0) {
+                FileKind::Binary
+            } else {
+                FileKind::Text
+            }
+        }
+        Err(_) => FileKind::Text,
+    }
+}
+
+/// Walk `root`, discovering every `.dxf`/`.jww` file. When `recursive` is
+/// false, only the immediate directory entries are considered. If `root` is
+/// itself a file, it is returned as the sole result.
+pub fn discover_files(root: &Path, recursive: bool) -> Vec<DiscoveredFile> {
+    if root.is_file() {
+        return vec![DiscoveredFile {
+            kind: sniff_kind(root),
+            path: root.to_path_buf(),
+        }];
+    }
+
+    let max_depth = if recursive { usize::MAX } else { 1 };
+
+    let mut files: Vec<DiscoveredFile> = WalkDir::new(root)
+        .max_depth(max_depth)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| CAD_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+                .unwrap_or(false)
+        })
+        .map(|entry| DiscoveredFile {
+            kind: sniff_kind(entry.path()),
+            path: entry.path().to_path_buf(),
+        })
+        .collect();
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
+
+/// A `Started`/`Finished` notification `run_parallel_with_progress` reports
+/// around each item's `process` call, delivered on the calling thread in
+/// true completion order (not `items` order).
+pub enum ProgressEvent<'a, I, T> {
+    Started {
+        index: usize,
+        total: usize,
+        item: &'a I,
+    },
+    Finished {
+        index: usize,
+        total: usize,
+        item: &'a I,
+        result: &'a T,
+    },
+}
+
+/// Run `process` over every element of `items`, spreading the work across up
+/// to `jobs` worker threads that pull their next index from a shared cursor
+/// (work-stealing, so a few slow items don't starve idle workers). Results
+/// are returned in the same order as `items` regardless of completion
+/// order. `on_event` is called, in true completion order, with a `Started`
+/// event just before and a `Finished` event just after each item's
+/// `process` call; pass a no-op closure when only the final `Vec` matters
+/// (that's what `run_parallel` below does).
+pub fn run_parallel_with_progress<I, T, F, E>(
+    items: &[I],
+    jobs: usize,
+    process: F,
+    mut on_event: E,
+) -> Vec<T>
+where
+    I: Sync,
+    T: Send,
+    F: Fn(&I) -> T + Send + Sync,
+    E: FnMut(ProgressEvent<I, T>),
+{
+    if items.is_empty() {
+        return Vec::new();
+    }
+
+    enum Message<T> {
+        Started(usize),
+        Done(usize, T),
+    }
+
+    let total = items.len();
+    let worker_count = jobs.max(1).min(total);
+    let next_index = Mutex::new(0usize);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..worker_count {
+            let tx = tx.clone();
+            let next_index = &next_index;
+            let process = &process;
+            scope.spawn(move || loop {
+                let index = {
+                    let mut next = next_index.lock().unwrap();
+                    if *next >= total {
+                        break;
+                    }
+                    let i = *next;
+                    *next += 1;
+                    i
+                };
+                tx.send(Message::Started(index)).ok();
+                let value = process(&items[index]);
+                tx.send(Message::Done(index, value)).ok();
+            });
+        }
+        drop(tx);
+
+        let mut results: Vec<Option<T>> = (0..total).map(|_| None).collect();
+        for message in rx.iter() {
+            match message {
+                Message::Started(index) => on_event(ProgressEvent::Started {
+                    index,
+                    total,
+                    item: &items[index],
+                }),
+                Message::Done(index, value) => {
+                    on_event(ProgressEvent::Finished {
+                        index,
+                        total,
+                        item: &items[index],
+                        result: &value,
+                    });
+                    results[index] = Some(value);
+                }
+            }
+        }
+        results.into_iter().map(|r| r.unwrap()).collect()
+    })
+}
+
+/// Run `process` over every item in `items`; see `run_parallel_with_progress`
+/// for the work-stealing strategy. Results are returned in the same order as
+/// `items` regardless of completion order.
+pub fn run_parallel<I, T, F>(items: &[I], jobs: usize, process: F) -> Vec<T>
+where
+    I: Sync,
+    T: Send,
+    F: Fn(&I) -> T + Send + Sync,
+{
+    run_parallel_with_progress(items, jobs, process, |_| {})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_parallel_returns_results_in_input_order_regardless_of_completion_order() {
+        let items: Vec<i32> = (0..20).collect();
+
+        // Make earlier items artificially slower than later ones so, absent
+        // storing each result in its item's index slot, completion order
+        // would scramble the result order.
+        let results = run_parallel(&items, 4, |&n| {
+            std::thread::sleep(std::time::Duration::from_micros((20 - n) as u64 * 200));
+            n * 2
+        });
+
+        assert_eq!(results, items.iter().map(|n| n * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn run_parallel_with_progress_reports_a_started_and_finished_event_per_item() {
+        let items = vec!["a", "b", "c"];
+        let started = Mutex::new(Vec::new());
+        let finished = Mutex::new(Vec::new());
+
+        let results = run_parallel_with_progress(
+            &items,
+            2,
+            |s| s.to_uppercase(),
+            |event| match event {
+                ProgressEvent::Started { index, item, .. } => {
+                    started.lock().unwrap().push((index, *item));
+                }
+                ProgressEvent::Finished {
+                    index,
+                    item,
+                    result,
+                    ..
+                } => {
+                    finished.lock().unwrap().push((index, *item, result.clone()));
+                }
+            },
+        );
+
+        assert_eq!(results, vec!["A", "B", "C"]);
+
+        let mut started = started.into_inner().unwrap();
+        started.sort();
+        assert_eq!(started, vec![(0, "a"), (1, "b"), (2, "c")]);
+
+        let mut finished = finished.into_inner().unwrap();
+        finished.sort_by_key(|(index, _, _)| *index);
+        assert_eq!(
+            finished,
+            vec![
+                (0, "a", "A".to_string()),
+                (1, "b", "B".to_string()),
+                (2, "c", "C".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn run_parallel_on_empty_input_returns_empty() {
+        let items: Vec<i32> = Vec::new();
+        let results = run_parallel(&items, 4, |&n| n);
+        assert!(results.is_empty());
+    }
+}
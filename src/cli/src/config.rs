@@ -0,0 +1,217 @@
+//! Rule selection and severity remapping for the validation engine
+//!
+//! Validation rules only assign a default severity to their findings:
+//! `validate_with_config` runs every rule, collects the raw diagnostics, then
+//! maps each one through a `RuleConfig` to get the severity that's actually
+//! reported. `cmd_validate` takes this path only when `--config` or
+//! `--preset` is given; without either, it reports `recad_core`'s own checks
+//! and the rules in `rules` are not run.
+
+use crate::ffi::{Issue, LcDetailLevel, LcSeverity, ValidationResult};
+use crate::rules;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A configured severity level for a rule, including `off` to suppress it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfiguredSeverity {
+    Off,
+    Level(LcSeverity),
+}
+
+impl FromStr for ConfiguredSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(ConfiguredSeverity::Off),
+            "info" => Ok(ConfiguredSeverity::Level(LcSeverity::Info)),
+            "warning" | "warn" => Ok(ConfiguredSeverity::Level(LcSeverity::Warning)),
+            "error" => Ok(ConfiguredSeverity::Level(LcSeverity::Error)),
+            other => Err(format!("Unknown severity: {other}")),
+        }
+    }
+}
+
+/// Rule selection and severity remapping, e.g. loaded from a `rules.toml`
+/// file like:
+///
+/// ```toml
+/// DXF_OPEN_POLYLINE = "warning"
+/// EMPTY_DRAWING = "off"
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RuleConfig {
+    overrides: HashMap<String, ConfiguredSeverity>,
+}
+
+impl RuleConfig {
+    /// Built-in preset that promotes every known rule to `error`
+    pub fn strict_preset() -> Self {
+        Self::from_pairs([
+            ("MISSING_LAYER", ConfiguredSeverity::Level(LcSeverity::Error)),
+            (
+                "INVALID_COLOR_INDEX",
+                ConfiguredSeverity::Level(LcSeverity::Error),
+            ),
+            ("DANGLING_HANDLE", ConfiguredSeverity::Level(LcSeverity::Error)),
+            (
+                "DXF_OPEN_POLYLINE",
+                ConfiguredSeverity::Level(LcSeverity::Error),
+            ),
+        ])
+    }
+
+    /// Built-in preset that downgrades advisory rules and suppresses
+    /// cosmetic ones
+    pub fn permissive_preset() -> Self {
+        Self::from_pairs([
+            (
+                "MISSING_LAYER",
+                ConfiguredSeverity::Level(LcSeverity::Warning),
+            ),
+            ("INVALID_COLOR_INDEX", ConfiguredSeverity::Off),
+        ])
+    }
+
+    /// Resolve a built-in preset by name (`strict`, `default`, `permissive`)
+    pub fn preset(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "strict" => Ok(Self::strict_preset()),
+            "default" => Ok(Self::default()),
+            "permissive" => Ok(Self::permissive_preset()),
+            other => Err(format!("Unknown preset: {other}")),
+        }
+    }
+
+    fn from_pairs<const N: usize>(pairs: [(&str, ConfiguredSeverity); N]) -> Self {
+        RuleConfig {
+            overrides: pairs
+                .into_iter()
+                .map(|(code, severity)| (code.to_string(), severity))
+                .collect(),
+        }
+    }
+
+    /// Load rule overrides from a TOML file
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        Self::from_toml_str(&contents)
+    }
+
+    fn from_toml_str(contents: &str) -> Result<Self, String> {
+        let raw: HashMap<String, String> =
+            toml::from_str(contents).map_err(|e| format!("invalid rule config: {e}"))?;
+
+        let overrides = raw
+            .into_iter()
+            .map(|(code, severity)| Ok((code, severity.parse()?)))
+            .collect::<Result<_, String>>()?;
+
+        Ok(RuleConfig { overrides })
+    }
+
+    /// Layer `other`'s overrides on top of this one (used to apply a
+    /// `--config` file on top of a `--preset`)
+    pub fn merged_with(mut self, other: RuleConfig) -> Self {
+        self.overrides.extend(other.overrides);
+        self
+    }
+
+    /// Resolve the severity this config assigns to `rule`, or `None` if the
+    /// rule is disabled.
+    fn apply(&self, rule: &str, default_severity: LcSeverity) -> Option<LcSeverity> {
+        match self.overrides.get(rule) {
+            None => Some(default_severity),
+            Some(ConfiguredSeverity::Off) => None,
+            Some(ConfiguredSeverity::Level(level)) => Some(*level),
+        }
+    }
+}
+
+/// Validate `filename` by combining `recad_core`'s structural checks with
+/// this crate's rule engine, remapping/suppressing each diagnostic's
+/// severity through `config` before recomputing `is_valid`.
+pub fn validate_with_config(filename: &str, config: &RuleConfig) -> Result<ValidationResult, String> {
+    let base = crate::ffi::validate(filename)?;
+    let info = crate::ffi::get_file_info(filename, LcDetailLevel::Full)?;
+
+    let mut issues: Vec<Issue> = Vec::new();
+
+    for issue in base.issues {
+        if let Some(severity) = config.apply(&issue.code, issue.severity) {
+            issues.push(Issue { severity, ..issue });
+        }
+    }
+
+    for diagnostic in rules::run_rules(&info) {
+        if let Some(severity) = config.apply(&diagnostic.rule, diagnostic.severity) {
+            issues.push(Issue {
+                severity,
+                code: diagnostic.rule,
+                message: diagnostic.message,
+                location: diagnostic.location,
+            });
+        }
+    }
+
+    let is_valid = !issues.iter().any(|i| i.severity == LcSeverity::Error);
+
+    Ok(ValidationResult { is_valid, issues })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_preset_promotes_missing_layer_to_error() {
+        let config = RuleConfig::preset("strict").unwrap();
+        assert_eq!(
+            config.apply("MISSING_LAYER", LcSeverity::Warning),
+            Some(LcSeverity::Error)
+        );
+    }
+
+    #[test]
+    fn permissive_preset_suppresses_invalid_color_index() {
+        let config = RuleConfig::preset("permissive").unwrap();
+        assert_eq!(config.apply("INVALID_COLOR_INDEX", LcSeverity::Warning), None);
+    }
+
+    #[test]
+    fn default_preset_leaves_unconfigured_rules_at_their_default_severity() {
+        let config = RuleConfig::default();
+        assert_eq!(
+            config.apply("MISSING_LAYER", LcSeverity::Error),
+            Some(LcSeverity::Error)
+        );
+    }
+
+    #[test]
+    fn unknown_preset_name_is_rejected() {
+        assert!(RuleConfig::preset("nonexistent").is_err());
+    }
+
+    #[test]
+    fn config_file_overrides_layer_on_top_of_a_preset() {
+        let preset = RuleConfig::strict_preset();
+        let file_override =
+            RuleConfig::from_toml_str("MISSING_LAYER = \"off\"").unwrap();
+        let merged = preset.merged_with(file_override);
+
+        assert_eq!(merged.apply("MISSING_LAYER", LcSeverity::Warning), None);
+        // Untouched by the file override, so the preset's value still applies.
+        assert_eq!(
+            merged.apply("INVALID_COLOR_INDEX", LcSeverity::Warning),
+            Some(LcSeverity::Error)
+        );
+    }
+
+    #[test]
+    fn invalid_severity_string_in_toml_is_rejected() {
+        assert!(RuleConfig::from_toml_str("MISSING_LAYER = \"extreme\"").is_err());
+    }
+}
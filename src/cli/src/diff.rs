@@ -0,0 +1,328 @@
+//! Entity-level diff between two DXF files
+
+use crate::ffi::{EntityInfo, FileInfo};
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// A single field that differs between the old and new revision of an entity
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: String,
+    pub new: String,
+}
+
+/// Minimal identifying information for an added/removed entity
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EntityRef {
+    pub key: String,
+    pub entity_type: String,
+    pub layer: String,
+}
+
+/// An entity present in both files with at least one changed field
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ChangedEntity {
+    #[serde(flatten)]
+    pub entity: EntityRef,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Entity-level comparison between two files
+#[derive(Debug, Clone, serde::Serialize, Default)]
+pub struct DiffResult {
+    pub added: Vec<EntityRef>,
+    pub removed: Vec<EntityRef>,
+    pub changed: Vec<ChangedEntity>,
+}
+
+/// Build a stable key for matching entities across the two files.
+///
+/// Entities with a non-zero DXF handle are matched by that handle; entities
+/// without one (or sharing a handle of 0) fall back to a hash of their
+/// layer/type/style/geometry so they can still be paired up across files.
+fn entity_key(entity: &EntityInfo) -> String {
+    if entity.handle != 0 {
+        return format!("#{:X}", entity.handle);
+    }
+
+    let mut hasher = DefaultHasher::new();
+    entity.entity_type.as_str().hash(&mut hasher);
+    entity.layer.hash(&mut hasher);
+    entity.color.hash(&mut hasher);
+    entity.line_type.hash(&mut hasher);
+    // EntityGeometry's fields are f64-based, and f64 doesn't implement Hash
+    // (NaN/-0.0 make a lawful impl impossible), so geometry is folded in via
+    // its Debug representation rather than a derived Hash. Without this,
+    // entities that only differ in geometry (e.g. two unhandled circles of
+    // different radii on the same layer) would collide on the same key.
+    format!("{:?}", entity.geometry).hash(&mut hasher);
+    format!("~{:016x}", hasher.finish())
+}
+
+fn entity_ref(key: &str, entity: &EntityInfo) -> EntityRef {
+    EntityRef {
+        key: key.to_string(),
+        entity_type: entity.entity_type.as_str().to_string(),
+        layer: entity.layer.clone(),
+    }
+}
+
+fn field_changes(old: &EntityInfo, new: &EntityInfo) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+
+    if old.layer != new.layer {
+        changes.push(FieldChange {
+            field: "layer".to_string(),
+            old: old.layer.clone(),
+            new: new.layer.clone(),
+        });
+    }
+    if old.color != new.color {
+        changes.push(FieldChange {
+            field: "color".to_string(),
+            old: old.color.to_string(),
+            new: new.color.to_string(),
+        });
+    }
+    if old.line_type != new.line_type {
+        changes.push(FieldChange {
+            field: "line_type".to_string(),
+            old: old.line_type.clone(),
+            new: new.line_type.clone(),
+        });
+    }
+    if (old.line_weight - new.line_weight).abs() > f64::EPSILON {
+        changes.push(FieldChange {
+            field: "line_weight".to_string(),
+            old: old.line_weight.to_string(),
+            new: new.line_weight.to_string(),
+        });
+    }
+    if old.geometry != new.geometry {
+        changes.push(FieldChange {
+            field: "geometry".to_string(),
+            old: format!("{:?}", old.geometry),
+            new: format!("{:?}", new.geometry),
+        });
+    }
+
+    changes
+}
+
+/// Compare two files' entity lists, matching by handle (or a content hash
+/// when no stable handle is available) and classifying each entity as
+/// added, removed, or modified.
+pub fn compute_diff(old: &FileInfo, new: &FileInfo) -> DiffResult {
+    let mut old_by_key: HashMap<String, &EntityInfo> = HashMap::new();
+    for entity in &old.entities {
+        old_by_key.insert(entity_key(entity), entity);
+    }
+
+    let mut new_by_key: HashMap<String, &EntityInfo> = HashMap::new();
+    for entity in &new.entities {
+        new_by_key.insert(entity_key(entity), entity);
+    }
+
+    let mut result = DiffResult::default();
+
+    for (key, new_entity) in &new_by_key {
+        match old_by_key.get(key) {
+            None => result.added.push(entity_ref(key, new_entity)),
+            Some(old_entity) => {
+                let changes = field_changes(old_entity, new_entity);
+                if !changes.is_empty() {
+                    result.changed.push(ChangedEntity {
+                        entity: entity_ref(key, new_entity),
+                        changes,
+                    });
+                }
+            }
+        }
+    }
+
+    for (key, old_entity) in &old_by_key {
+        if !new_by_key.contains_key(key) {
+            result.removed.push(entity_ref(key, old_entity));
+        }
+    }
+
+    result.added.sort_by(|a, b| a.key.cmp(&b.key));
+    result.removed.sort_by(|a, b| a.key.cmp(&b.key));
+    result.changed.sort_by(|a, b| a.entity.key.cmp(&b.entity.key));
+
+    result
+}
+
+/// Render a human-readable summary of a diff, grouped by layer with
+/// +/- counts, followed by the detailed added/removed/changed entity lists.
+pub fn write_diff(out: &mut impl std::fmt::Write, diff: &DiffResult) -> std::fmt::Result {
+    use std::collections::BTreeMap;
+
+    let mut by_layer: BTreeMap<&str, (usize, usize, usize)> = BTreeMap::new();
+    for e in &diff.added {
+        by_layer.entry(&e.layer).or_default().0 += 1;
+    }
+    for e in &diff.removed {
+        by_layer.entry(&e.layer).or_default().1 += 1;
+    }
+    for e in &diff.changed {
+        by_layer.entry(&e.entity.layer).or_default().2 += 1;
+    }
+
+    writeln!(out, "Layer Summary")?;
+    writeln!(out, "-------------")?;
+    for (layer, (added, removed, changed)) in &by_layer {
+        writeln!(
+            out,
+            "  {:16} +{:<4} -{:<4} ~{:<4}",
+            layer, added, removed, changed
+        )?;
+    }
+    writeln!(out)?;
+
+    if !diff.added.is_empty() {
+        writeln!(out, "Added ({})", diff.added.len())?;
+        for e in &diff.added {
+            writeln!(out, "  + {} {} (layer: {})", e.key, e.entity_type, e.layer)?;
+        }
+        writeln!(out)?;
+    }
+
+    if !diff.removed.is_empty() {
+        writeln!(out, "Removed ({})", diff.removed.len())?;
+        for e in &diff.removed {
+            writeln!(out, "  - {} {} (layer: {})", e.key, e.entity_type, e.layer)?;
+        }
+        writeln!(out)?;
+    }
+
+    if !diff.changed.is_empty() {
+        writeln!(out, "Changed ({})", diff.changed.len())?;
+        for c in &diff.changed {
+            writeln!(
+                out,
+                "  ~ {} {} (layer: {})",
+                c.entity.key, c.entity.entity_type, c.entity.layer
+            )?;
+            for change in &c.changes {
+                writeln!(
+                    out,
+                    "      {}: {} -> {}",
+                    change.field, change.old, change.new
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi::{EntityGeometry, LcEntityType};
+
+    fn entity(handle: i32, color: i32, geometry: EntityGeometry) -> EntityInfo {
+        EntityInfo {
+            entity_type: LcEntityType::Circle,
+            layer: "0".to_string(),
+            color,
+            line_type: "CONTINUOUS".to_string(),
+            line_weight: 0.0,
+            handle,
+            geometry,
+        }
+    }
+
+    #[test]
+    fn handle_less_entities_differing_only_in_geometry_get_distinct_keys() {
+        let a = entity(
+            0,
+            1,
+            EntityGeometry::Circle {
+                center: (0.0, 0.0, 0.0),
+                radius: 1.0,
+            },
+        );
+        let b = entity(
+            0,
+            1,
+            EntityGeometry::Circle {
+                center: (0.0, 0.0, 0.0),
+                radius: 2.0,
+            },
+        );
+
+        assert_ne!(entity_key(&a), entity_key(&b));
+    }
+
+    #[test]
+    fn handle_less_entities_differing_only_in_line_endpoint_get_distinct_keys() {
+        let a = entity(
+            0,
+            1,
+            EntityGeometry::Line {
+                start: (0.0, 0.0, 0.0),
+                end: (1.0, 0.0, 0.0),
+            },
+        );
+        let b = entity(
+            0,
+            1,
+            EntityGeometry::Line {
+                start: (0.0, 0.0, 0.0),
+                end: (2.0, 0.0, 0.0),
+            },
+        );
+
+        assert_ne!(entity_key(&a), entity_key(&b));
+    }
+
+    #[test]
+    fn colliding_handle_less_entities_are_both_reported_as_added() {
+        // Two un-handled circles on the same layer that only differ in
+        // radius must not collide on the same fallback key (see
+        // `entity_key`), or one would silently overwrite the other here.
+        let new_entities = vec![
+            entity(
+                0,
+                1,
+                EntityGeometry::Circle {
+                    center: (0.0, 0.0, 0.0),
+                    radius: 1.0,
+                },
+            ),
+            entity(
+                0,
+                1,
+                EntityGeometry::Circle {
+                    center: (0.0, 0.0, 0.0),
+                    radius: 2.0,
+                },
+            ),
+        ];
+
+        let old = FileInfo {
+            filename: "old.dxf".to_string(),
+            format: crate::ffi::LcFormat::Dxf,
+            dxf_version: "R2000".to_string(),
+            layer_count: 0,
+            block_count: 0,
+            entity_count: 0,
+            bounds: ((0.0, 0.0, 0.0), (0.0, 0.0, 0.0)),
+            layers: Vec::new(),
+            blocks: Vec::new(),
+            entities: Vec::new(),
+            entity_counts: [0; 20],
+        };
+        let new = FileInfo {
+            entities: new_entities,
+            ..old.clone()
+        };
+
+        let diff = compute_diff(&old, &new);
+        assert_eq!(diff.added.len(), 2);
+    }
+}
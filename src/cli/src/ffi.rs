@@ -43,6 +43,14 @@ impl LcFormat {
     }
 }
 
+// Serialized by name rather than by derive so the encoding stays stable even
+// if `LcFormat`'s discriminants or variant order ever change on the C side.
+impl serde::Serialize for LcFormat {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
 /// DXF version for export
 #[repr(C)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -129,6 +137,14 @@ impl LcEntityType {
     }
 }
 
+// See the `LcFormat` impl above: serialize by name, not by derive, so the
+// encoding is stable across changes to the C-side discriminants.
+impl serde::Serialize for LcEntityType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
 /// Validation severity levels
 #[repr(C)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -140,7 +156,6 @@ pub enum LcSeverity {
 }
 
 impl LcSeverity {
-    #[allow(dead_code)]
     pub fn as_str(&self) -> &'static str {
         match self {
             LcSeverity::Info => "info",
@@ -150,6 +165,14 @@ impl LcSeverity {
     }
 }
 
+// See the `LcFormat` impl above: serialize by name, not by derive, so the
+// encoding is stable across changes to the C-side discriminants.
+impl serde::Serialize for LcSeverity {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
 /// Detail levels for info output
 #[repr(C)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -345,6 +368,71 @@ pub fn convert(input: &str, output: &str, dxf_version: LcDxfVersion) -> Result<(
     }
 }
 
+/// A progress event emitted by `convert_batch` for one file in the batch,
+/// carrying that file's `index` in `jobs` and the batch's `total` size.
+/// No running count of completed files is included.
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub enum ConvertProgress {
+    Started { index: usize, total: usize, input: String },
+    Finished { index: usize, total: usize, input: String },
+    Failed { index: usize, total: usize, input: String, error: String },
+}
+
+/// Convert every `(input, output)` pair in `jobs` to `dxf_version`,
+/// optionally spreading the work across up to `workers` threads (safe since
+/// each `lc_convert` call only touches its own C strings) via
+/// `batch::run_parallel_with_progress`. `progress` is called, in order, for
+/// every file's start and finish/failure as it happens; one bad file does
+/// not stop the rest, and the per-file outcome is returned in `jobs` order
+/// regardless of completion order.
+#[allow(dead_code)]
+pub fn convert_batch(
+    jobs: &[(String, String)],
+    dxf_version: LcDxfVersion,
+    workers: usize,
+    mut progress: impl FnMut(ConvertProgress),
+) -> Vec<Result<(), String>> {
+    crate::batch::run_parallel_with_progress(
+        jobs,
+        workers,
+        |(input, output)| convert(input, output, dxf_version),
+        |event| {
+            let progress_event = match event {
+                crate::batch::ProgressEvent::Started { index, total, item } => {
+                    ConvertProgress::Started {
+                        index,
+                        total,
+                        input: item.0.clone(),
+                    }
+                }
+                crate::batch::ProgressEvent::Finished {
+                    index,
+                    total,
+                    item,
+                    result: Ok(()),
+                } => ConvertProgress::Finished {
+                    index,
+                    total,
+                    input: item.0.clone(),
+                },
+                crate::batch::ProgressEvent::Finished {
+                    index,
+                    total,
+                    item,
+                    result: Err(error),
+                } => ConvertProgress::Failed {
+                    index,
+                    total,
+                    input: item.0.clone(),
+                    error: error.clone(),
+                },
+            };
+            progress(progress_event);
+        },
+    )
+}
+
 /// Get file info as JSON string
 pub fn get_file_info_json(filename: &str, detail: LcDetailLevel) -> Result<String, String> {
     let c_filename = CString::new(filename).unwrap();
@@ -427,10 +515,62 @@ pub fn validate(filename: &str) -> Result<ValidationResult, String> {
     }
 }
 
+/// Output encoding for the Rust-struct-direct serialization path
+/// (`get_file_info_serialized` / `validate_serialized`), as opposed to the
+/// C-controlled schema `lc_file_info_to_json` produces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum SerializeFormat {
+    Json,
+    Yaml,
+}
+
+impl std::str::FromStr for SerializeFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(SerializeFormat::Json),
+            "yaml" | "yml" => Ok(SerializeFormat::Yaml),
+            other => Err(format!("Unknown serialize format: {other}")),
+        }
+    }
+}
+
+impl SerializeFormat {
+    fn encode<T: serde::Serialize>(&self, value: &T) -> Result<String, String> {
+        match self {
+            SerializeFormat::Json => {
+                serde_json::to_string_pretty(value).map_err(|e| e.to_string())
+            }
+            SerializeFormat::Yaml => serde_yaml::to_string(value).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// Get file info and serialize it straight from the Rust struct, in JSON or
+/// YAML, without round-tripping through `lc_file_info_to_json`.
+#[allow(dead_code)]
+pub fn get_file_info_serialized(
+    filename: &str,
+    detail: LcDetailLevel,
+    format: SerializeFormat,
+) -> Result<String, String> {
+    format.encode(&get_file_info(filename, detail)?)
+}
+
+/// Validate a file and serialize the result straight from the Rust struct,
+/// in JSON or YAML, without round-tripping through
+/// `lc_validation_result_to_json`.
+#[allow(dead_code)]
+pub fn validate_serialized(filename: &str, format: SerializeFormat) -> Result<String, String> {
+    format.encode(&validate(filename)?)
+}
+
 // High-level Rust types
 
 /// Layer information (Rust-owned)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct LayerInfo {
     pub name: String,
     pub color: i32,
@@ -443,29 +583,104 @@ pub struct LayerInfo {
 }
 
 /// Block information (Rust-owned)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct BlockInfo {
     pub name: String,
     pub base_point: (f64, f64, f64),
     pub entity_count: i32,
 }
 
+/// Decoded geometry from `LcEntityInfo.data`, the opaque 56-byte union the C
+/// ABI hands back. Each variant's fields are read from fixed little-endian
+/// offsets within that buffer; entity types this crate doesn't yet decode
+/// report `Unknown` rather than guessing at a layout.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(tag = "kind")]
+pub enum EntityGeometry {
+    Point {
+        x: f64,
+        y: f64,
+        z: f64,
+    },
+    Line {
+        start: (f64, f64, f64),
+        end: (f64, f64, f64),
+    },
+    Circle {
+        center: (f64, f64, f64),
+        radius: f64,
+    },
+    Arc {
+        center: (f64, f64, f64),
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+    },
+    /// Vertex coordinates are not decoded: an LWPolyline's vertex list is
+    /// variable-length and doesn't fit in the fixed 56-byte `data` union
+    /// other variants read from, so only the fixed-size header fields
+    /// (count and the closed flag) are available here. Known limitation,
+    /// not an oversight.
+    LwPolyline {
+        vertex_count: u32,
+        closed: bool,
+    },
+    Unknown,
+}
+
+/// Read a little-endian `f64` out of `data` at `offset`.
+///
+/// `data` is always exactly 56 bytes and every offset used by `decode` below
+/// is a fixed, in-range constant, so this never panics in practice.
+fn read_f64(data: &[u8; 56], offset: usize) -> f64 {
+    f64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+impl EntityGeometry {
+    fn decode(entity_type: LcEntityType, data: &[u8; 56]) -> Self {
+        match entity_type {
+            LcEntityType::Point => EntityGeometry::Point {
+                x: read_f64(data, 0),
+                y: read_f64(data, 8),
+                z: read_f64(data, 16),
+            },
+            LcEntityType::Line => EntityGeometry::Line {
+                start: (read_f64(data, 0), read_f64(data, 8), read_f64(data, 16)),
+                end: (read_f64(data, 24), read_f64(data, 32), read_f64(data, 40)),
+            },
+            LcEntityType::Circle => EntityGeometry::Circle {
+                center: (read_f64(data, 0), read_f64(data, 8), read_f64(data, 16)),
+                radius: read_f64(data, 24),
+            },
+            LcEntityType::Arc => EntityGeometry::Arc {
+                center: (read_f64(data, 0), read_f64(data, 8), read_f64(data, 16)),
+                radius: read_f64(data, 24),
+                start_angle: read_f64(data, 32),
+                end_angle: read_f64(data, 40),
+            },
+            LcEntityType::LwPolyline => EntityGeometry::LwPolyline {
+                vertex_count: u32::from_le_bytes(data[0..4].try_into().unwrap()),
+                closed: data[4] != 0,
+            },
+            _ => EntityGeometry::Unknown,
+        }
+    }
+}
+
 /// Entity information (Rust-owned)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct EntityInfo {
     pub entity_type: LcEntityType,
     pub layer: String,
     pub color: i32,
-    #[allow(dead_code)]
     pub line_type: String,
-    #[allow(dead_code)]
     pub line_weight: f64,
-    #[allow(dead_code)]
     pub handle: i32,
+    pub geometry: EntityGeometry,
 }
 
 /// File information (Rust-owned)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct FileInfo {
     pub filename: String,
     pub format: LcFormat,
@@ -565,6 +780,7 @@ impl FileInfo {
                     },
                     line_weight: entity.line_weight,
                     handle: entity.handle,
+                    geometry: EntityGeometry::decode(entity.entity_type, &entity.data),
                 });
             }
         }
@@ -589,7 +805,7 @@ impl FileInfo {
 }
 
 /// Validation issue (Rust-owned)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct Issue {
     pub severity: LcSeverity,
     pub code: String,
@@ -598,7 +814,7 @@ pub struct Issue {
 }
 
 /// Validation result (Rust-owned)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct ValidationResult {
     pub is_valid: bool,
     pub issues: Vec<Issue>,
@@ -772,4 +988,82 @@ mod tests {
         assert_eq!(LcSeverity::Warning as i32, 1);
         assert_eq!(LcSeverity::Error as i32, 2);
     }
+
+    fn geometry_bytes(fields: &[f64]) -> [u8; 56] {
+        let mut data = [0u8; 56];
+        for (i, value) in fields.iter().enumerate() {
+            data[i * 8..i * 8 + 8].copy_from_slice(&value.to_le_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn test_decode_point_geometry() {
+        let data = geometry_bytes(&[1.0, 2.0, 3.0]);
+        assert_eq!(
+            EntityGeometry::decode(LcEntityType::Point, &data),
+            EntityGeometry::Point { x: 1.0, y: 2.0, z: 3.0 }
+        );
+    }
+
+    #[test]
+    fn test_decode_line_geometry() {
+        let data = geometry_bytes(&[0.0, 0.0, 0.0, 10.0, 0.0, 0.0]);
+        assert_eq!(
+            EntityGeometry::decode(LcEntityType::Line, &data),
+            EntityGeometry::Line {
+                start: (0.0, 0.0, 0.0),
+                end: (10.0, 0.0, 0.0),
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_circle_geometry() {
+        let data = geometry_bytes(&[1.0, 1.0, 0.0, 5.0]);
+        assert_eq!(
+            EntityGeometry::decode(LcEntityType::Circle, &data),
+            EntityGeometry::Circle {
+                center: (1.0, 1.0, 0.0),
+                radius: 5.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_arc_geometry() {
+        let data = geometry_bytes(&[0.0, 0.0, 0.0, 2.5, 0.0, 180.0]);
+        assert_eq!(
+            EntityGeometry::decode(LcEntityType::Arc, &data),
+            EntityGeometry::Arc {
+                center: (0.0, 0.0, 0.0),
+                radius: 2.5,
+                start_angle: 0.0,
+                end_angle: 180.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_lwpolyline_geometry() {
+        let mut data = [0u8; 56];
+        data[0..4].copy_from_slice(&4u32.to_le_bytes());
+        data[4] = 1;
+        assert_eq!(
+            EntityGeometry::decode(LcEntityType::LwPolyline, &data),
+            EntityGeometry::LwPolyline {
+                vertex_count: 4,
+                closed: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_unknown_geometry() {
+        let data = [0u8; 56];
+        assert_eq!(
+            EntityGeometry::decode(LcEntityType::Text, &data),
+            EntityGeometry::Unknown
+        );
+    }
 }
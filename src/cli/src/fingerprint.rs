@@ -0,0 +1,138 @@
+//! Content-hash fingerprinting of decoded drawing geometry
+//!
+//! `fingerprint` lets callers tell whether two CAD files — e.g. a DWG and
+//! the DXF it was converted to — represent the same drawing, independent of
+//! entity ordering, handle assignment, or which format encoded them.
+
+use crate::ffi::{self, EntityGeometry, LcDetailLevel};
+use sha2::{Digest, Sha256};
+
+/// Grid step coordinates are rounded to, to absorb the rounding noise a
+/// format round-trip (e.g. DXF -> DWG -> DXF) introduces. Coordinates that
+/// round to the same multiple of this step are treated as equal; two values
+/// closer than this step can still land on neighbouring grid points.
+const COORD_EPSILON: f64 = 1e-6;
+
+fn quantize(value: f64) -> i64 {
+    (value / COORD_EPSILON).round() as i64
+}
+
+/// A canonical, orderable form of an entity's geometry — not its handle or
+/// color — used both to sort entities deterministically and as the basis
+/// for the fingerprint's byte stream.
+fn canonical_key(geometry: &EntityGeometry) -> Vec<i64> {
+    match geometry {
+        EntityGeometry::Point { x, y, z } => vec![0, quantize(*x), quantize(*y), quantize(*z)],
+        EntityGeometry::Line { start, end } => vec![
+            1,
+            quantize(start.0),
+            quantize(start.1),
+            quantize(start.2),
+            quantize(end.0),
+            quantize(end.1),
+            quantize(end.2),
+        ],
+        EntityGeometry::Circle { center, radius } => vec![
+            2,
+            quantize(center.0),
+            quantize(center.1),
+            quantize(center.2),
+            quantize(*radius),
+        ],
+        EntityGeometry::Arc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+        } => vec![
+            3,
+            quantize(center.0),
+            quantize(center.1),
+            quantize(center.2),
+            quantize(*radius),
+            quantize(*start_angle),
+            quantize(*end_angle),
+        ],
+        EntityGeometry::LwPolyline {
+            vertex_count,
+            closed,
+        } => vec![4, *vertex_count as i64, if *closed { 1 } else { 0 }],
+        EntityGeometry::Unknown => vec![5],
+    }
+}
+
+fn canonical_bytes(key: &[i64]) -> Vec<u8> {
+    key.iter().flat_map(|component| component.to_le_bytes()).collect()
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Compute a content fingerprint for `filename`'s decoded geometry: a fast
+/// CRC32 plus a SHA-256 digest over a canonical byte stream built from every
+/// entity's geometry, sorted by a stable `(type, quantized coordinates)` key
+/// so that entity order and handle assignment never affect the result.
+///
+/// Returns `"<crc32 hex>:<sha256 hex>"`.
+#[allow(dead_code)]
+pub fn fingerprint(filename: &str) -> Result<String, String> {
+    let info = ffi::get_file_info(filename, LcDetailLevel::Full)?;
+
+    let mut keys: Vec<Vec<i64>> = info
+        .entities
+        .iter()
+        .map(|entity| canonical_key(&entity.geometry))
+        .collect();
+    keys.sort();
+
+    let stream: Vec<u8> = keys.iter().flat_map(|key| canonical_bytes(key)).collect();
+
+    let crc = crc32fast::hash(&stream);
+
+    let mut hasher = Sha256::new();
+    hasher.update(&stream);
+    let digest = hasher.finalize();
+
+    Ok(format!("{crc:08x}:{}", hex_encode(&digest)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quantize_rounds_coordinates_within_half_epsilon_to_the_same_value() {
+        assert_eq!(quantize(1.0), quantize(1.0 + COORD_EPSILON / 4.0));
+    }
+
+    #[test]
+    fn quantize_distinguishes_coordinates_beyond_epsilon() {
+        assert_ne!(quantize(1.0), quantize(1.0 + COORD_EPSILON * 10.0));
+    }
+
+    #[test]
+    fn canonical_key_differs_by_geometry_variant_even_with_matching_numbers() {
+        let point = canonical_key(&EntityGeometry::Point { x: 1.0, y: 0.0, z: 0.0 });
+        let circle = canonical_key(&EntityGeometry::Circle {
+            center: (1.0, 0.0, 0.0),
+            radius: 0.0,
+        });
+
+        assert_ne!(point, circle);
+    }
+
+    #[test]
+    fn canonical_key_is_stable_for_equivalent_lwpolylines() {
+        let a = canonical_key(&EntityGeometry::LwPolyline { vertex_count: 4, closed: true });
+        let b = canonical_key(&EntityGeometry::LwPolyline { vertex_count: 4, closed: true });
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn canonical_bytes_round_trips_key_length() {
+        let key = canonical_key(&EntityGeometry::Point { x: 1.0, y: 2.0, z: 3.0 });
+        assert_eq!(canonical_bytes(&key).len(), key.len() * 8);
+    }
+}
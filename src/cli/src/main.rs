@@ -3,15 +3,99 @@
 //! An unofficial CAD file utility tool for format conversion,
 //! file information extraction, and validation.
 
+#[cfg(any(
+    feature = "compress-zstd",
+    feature = "compress-bzip2",
+    feature = "compress-xz"
+))]
+mod archive;
+mod batch;
+mod config;
+mod diff;
 mod ffi;
+mod fingerprint;
+mod rules;
+mod sarif;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use colored::*;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
 use ffi::{LcDetailLevel, LcDxfVersion, LcEntityType, LcSeverity};
 
+/// Severity threshold at/above which `validate` should exit non-zero
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FailOn {
+    Error,
+    Warning,
+    Info,
+    Never,
+}
+
+impl std::str::FromStr for FailOn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "error" => Ok(FailOn::Error),
+            "warning" => Ok(FailOn::Warning),
+            "info" => Ok(FailOn::Info),
+            "never" => Ok(FailOn::Never),
+            other => Err(format!("Unknown --fail-on level: {other}")),
+        }
+    }
+}
+
+fn severity_rank(severity: LcSeverity) -> u8 {
+    match severity {
+        LcSeverity::Info => 0,
+        LcSeverity::Warning => 1,
+        LcSeverity::Error => 2,
+    }
+}
+
+/// Same ranking as [`severity_rank`], for severities that only survive as
+/// the `"info"`/`"warning"`/`"error"` strings embedded in a per-file JSON
+/// blob (as in `cmd_validate_batch`, which never deserializes back into
+/// `ffi::Issue`).
+fn severity_rank_str(severity: &str) -> u8 {
+    match severity {
+        "error" => 2,
+        "warning" => 1,
+        _ => 0,
+    }
+}
+
+/// Compute the process exit code for a worst-case severity rank against a
+/// `--fail-on` threshold: 0 when clean (or `never`), otherwise a distinct
+/// non-zero code per worst severity bucket present.
+fn fail_on_exit_code_for_rank(worst_rank: Option<u8>, fail_on: FailOn) -> ExitCode {
+    let threshold = match fail_on {
+        FailOn::Never => return ExitCode::SUCCESS,
+        FailOn::Error => severity_rank(LcSeverity::Error),
+        FailOn::Warning => severity_rank(LcSeverity::Warning),
+        FailOn::Info => severity_rank(LcSeverity::Info),
+    };
+
+    match worst_rank {
+        Some(rank) if rank >= threshold => ExitCode::from(match rank {
+            2 => 1,
+            1 => 2,
+            _ => 3,
+        }),
+        _ => ExitCode::SUCCESS,
+    }
+}
+
+/// Compute the process exit code for a set of validation issues against a
+/// `--fail-on` threshold.
+fn fail_on_exit_code(issues: &[ffi::Issue], fail_on: FailOn) -> ExitCode {
+    let worst_rank = issues.iter().map(|i| severity_rank(i.severity)).max();
+    fail_on_exit_code_for_rank(worst_rank, fail_on)
+}
+
 #[derive(Parser)]
 #[command(name = "cadutil")]
 #[command(author = "CAD Utility Contributors")]
@@ -39,7 +123,7 @@ enum Commands {
 
     /// Display file information
     Info {
-        /// Input file to analyze
+        /// Input file, or a directory to scan, to analyze
         input: PathBuf,
 
         /// Detail level (summary, normal, verbose, full)
@@ -49,23 +133,101 @@ enum Commands {
         /// Output as JSON
         #[arg(short, long)]
         json: bool,
+
+        /// Recurse into subdirectories when input is a directory
+        #[arg(long)]
+        recursive: bool,
+
+        /// Number of files to process in parallel when input is a directory
+        #[arg(long, default_value_t = 1)]
+        jobs: usize,
     },
 
     /// Validate a DXF file
     Validate {
-        /// Input file to validate
+        /// Input file, or a directory to scan, to validate
         input: PathBuf,
 
         /// Output as JSON
         #[arg(short, long)]
         json: bool,
+
+        /// Recurse into subdirectories when input is a directory
+        #[arg(long)]
+        recursive: bool,
+
+        /// Number of files to process in parallel when input is a directory
+        #[arg(long, default_value_t = 1)]
+        jobs: usize,
+
+        /// Write a repaired copy of the file to this path, applying any
+        /// non-conflicting fixes the rule engine finds.
+        ///
+        /// NOT IMPLEMENTED — always rejected by `validate_flag_combination`,
+        /// never writes a file. Tracked as blocked, not delivered: the
+        /// write path this flag needs is the remaining scope of backlog
+        /// items chunk0-3 ("Auto-repair mode for validate") and chunk1-1's
+        /// `--fix` half ("Rule-based validation engine with `--fix` autofix
+        /// mode"). `recad_core` exposes no per-entity mutation API (only
+        /// whole-document open/save/validate/convert), so there is no way
+        /// to apply a selected fix and re-serialize the result until that
+        /// capability exists upstream; use `--dry-run` to see what would be
+        /// fixed instead.
+        #[arg(long)]
+        fix: Option<PathBuf>,
+
+        /// List the fixes that would be applied without writing a file
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Path to a rules.toml file enabling/disabling rules or
+        /// overriding their reported severity
+        #[arg(long)]
+        config: Option<PathBuf>,
+
+        /// Built-in rule preset (strict, default, permissive)
+        #[arg(long)]
+        preset: Option<String>,
+
+        /// Exit non-zero when an issue at or above this severity is found
+        /// (error, warning, info, never)
+        #[arg(long, default_value = "error")]
+        fail_on: String,
+
+        /// Output format for issues (sarif), for feeding code-scanning tools.
+        /// Takes precedence over --json when given.
+        #[arg(long)]
+        format: Option<String>,
+    },
+
+    /// Compare two DXF files entity-by-entity
+    Diff {
+        /// Original file
+        old: PathBuf,
+
+        /// New file
+        new: PathBuf,
+
+        /// Output as JSON
+        #[arg(short, long)]
+        json: bool,
     },
 
     /// Show library version
     Version,
 }
 
-fn main() -> Result<()> {
+fn main() -> ExitCode {
+    match run() {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<ExitCode> {
     let cli = Cli::parse();
 
     match cli.command {
@@ -73,20 +235,37 @@ fn main() -> Result<()> {
             input,
             output,
             dxf_version,
-        } => cmd_convert(&input, &output, &dxf_version),
+        } => cmd_convert(&input, &output, &dxf_version).map(|_| ExitCode::SUCCESS),
 
         Commands::Info {
             input,
             detail,
             json,
-        } => cmd_info(&input, &detail, json),
+            recursive,
+            jobs,
+        } => cmd_info(&input, &detail, json, recursive, jobs).map(|_| ExitCode::SUCCESS),
 
-        Commands::Validate { input, json } => cmd_validate(&input, json),
+        Commands::Validate {
+            input,
+            json,
+            recursive,
+            jobs,
+            fix,
+            dry_run,
+            config,
+            preset,
+            fail_on,
+            format,
+        } => cmd_validate(
+            &input, json, recursive, jobs, fix, dry_run, config, preset, &fail_on, format.as_deref(),
+        ),
+
+        Commands::Diff { old, new, json } => cmd_diff(&old, &new, json).map(|_| ExitCode::SUCCESS),
 
         Commands::Version => {
             println!("cadutil {}", env!("CARGO_PKG_VERSION"));
             println!("cadutil_core {}", ffi::version());
-            Ok(())
+            Ok(ExitCode::SUCCESS)
         }
     }
 }
@@ -121,13 +300,17 @@ fn cmd_convert(input: &PathBuf, output: &PathBuf, dxf_version: &str) -> Result<(
     Ok(())
 }
 
-fn cmd_info(input: &PathBuf, detail: &str, json: bool) -> Result<()> {
-    let input_str = input.to_string_lossy();
-
+fn cmd_info(input: &PathBuf, detail: &str, json: bool, recursive: bool, jobs: usize) -> Result<()> {
     let detail_level: LcDetailLevel = detail
         .parse()
         .map_err(|e: String| anyhow::anyhow!("{}", e))?;
 
+    if input.is_dir() {
+        return cmd_info_batch(input, detail_level, recursive, jobs);
+    }
+
+    let input_str = input.to_string_lossy();
+
     if json {
         let json_output = ffi::get_file_info_json(&input_str, detail_level)
             .map_err(|e| anyhow::anyhow!("Failed to get file info: {}", e))?;
@@ -142,6 +325,53 @@ fn cmd_info(input: &PathBuf, detail: &str, json: bool) -> Result<()> {
     Ok(())
 }
 
+/// Walk a directory of CAD files and emit one aggregated JSON document:
+/// a per-file `info --json` result plus a rollup of totals and errors.
+fn cmd_info_batch(root: &PathBuf, detail: LcDetailLevel, recursive: bool, jobs: usize) -> Result<()> {
+    let files = batch::discover_files(root, recursive);
+
+    let per_file: Vec<serde_json::Value> = batch::run_parallel(&files, jobs, |file| {
+        let path_str = file.path.to_string_lossy().to_string();
+        match ffi::get_file_info_json(&path_str, detail) {
+            Ok(raw) => serde_json::from_str::<serde_json::Value>(&raw).unwrap_or_else(|_| {
+                serde_json::json!({ "file": path_str, "kind": file.kind, "raw": raw })
+            }),
+            Err(e) => serde_json::json!({ "file": path_str, "kind": file.kind, "error": e }),
+        }
+    });
+
+    let mut total_entities = 0i64;
+    let mut total_layers = 0i64;
+    let mut errors = Vec::new();
+
+    for value in &per_file {
+        match value.get("error").and_then(|e| e.as_str()) {
+            Some(err) => errors.push(serde_json::json!({
+                "file": value.get("file"),
+                "error": err,
+            })),
+            None => {
+                total_entities += value.get("entity_count").and_then(|v| v.as_i64()).unwrap_or(0);
+                total_layers += value.get("layer_count").and_then(|v| v.as_i64()).unwrap_or(0);
+            }
+        }
+    }
+
+    let report = serde_json::json!({
+        "files": per_file,
+        "summary": {
+            "total_files": files.len(),
+            "total_entities": total_entities,
+            "total_layers": total_layers,
+            "invalid_files": errors.len(),
+            "errors": errors,
+        },
+    });
+
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
+
 fn print_file_info(info: &ffi::FileInfo, detail: LcDetailLevel) {
     println!("{}", "File Information".cyan().bold());
     println!("{}", "================".cyan());
@@ -268,18 +498,281 @@ fn print_file_info(info: &ffi::FileInfo, detail: LcDetailLevel) {
     }
 }
 
-fn cmd_validate(input: &PathBuf, json: bool) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+fn cmd_validate(
+    input: &PathBuf,
+    json: bool,
+    recursive: bool,
+    jobs: usize,
+    fix: Option<PathBuf>,
+    dry_run: bool,
+    config: Option<PathBuf>,
+    preset: Option<String>,
+    fail_on: &str,
+    format: Option<&str>,
+) -> Result<ExitCode> {
+    let fail_on: FailOn = fail_on
+        .parse()
+        .map_err(|e: String| anyhow::anyhow!("{}", e))?;
+
+    validate_flag_combination(input.is_dir(), fix.as_deref(), dry_run, format)
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    if input.is_dir() {
+        let rule_config = if config.is_some() || preset.is_some() {
+            Some(resolve_rule_config(preset.as_deref(), config.as_ref())?)
+        } else {
+            None
+        };
+        return cmd_validate_batch(input, recursive, jobs, fail_on, rule_config.as_ref());
+    }
+
     let input_str = input.to_string_lossy();
 
-    if json {
-        let json_output = ffi::validate_json(&input_str)
+    if dry_run {
+        let info = ffi::get_file_info(&input_str, LcDetailLevel::Full)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", input_str, e))?;
+        return cmd_validate_fix(&info);
+    }
+
+    if config.is_some() || preset.is_some() {
+        let rule_config = resolve_rule_config(preset.as_deref(), config.as_ref())?;
+        let result = config::validate_with_config(&input_str, &rule_config)
             .map_err(|e| anyhow::anyhow!("Validation failed: {}", e))?;
-        println!("{}", json_output);
+
+        print_validate_output(&result, &input_str, json, format)?;
+
+        return Ok(fail_on_exit_code(&result.issues, fail_on));
+    }
+
+    let result = ffi::validate(&input_str)
+        .map_err(|e| anyhow::anyhow!("Validation failed: {}", e))?;
+
+    print_validate_output(&result, &input_str, json, format)?;
+
+    Ok(fail_on_exit_code(&result.issues, fail_on))
+}
+
+/// Reject mutually-incompatible `validate` flag combinations up front,
+/// before touching the filesystem: an unknown `--format`, `--fix` (always
+/// blocked, see the `fix` field doc comment on `Commands::Validate`),
+/// `--format`+`--dry-run`, and `--dry-run`/`--format` paired with a
+/// directory input (batch mode has no single-file fix-preview or SARIF
+/// aggregation story).
+fn validate_flag_combination(
+    is_dir: bool,
+    fix: Option<&Path>,
+    dry_run: bool,
+    format: Option<&str>,
+) -> Result<(), String> {
+    if let Some(format) = format {
+        if format != "sarif" {
+            return Err(format!("Unknown --format: {format} (expected `sarif`)"));
+        }
+    }
+
+    if let Some(output) = fix {
+        return Err(format!(
+            "--fix {} is blocked: recad_core has no per-entity mutation API, so a \
+             selected fix can never be applied and written by this build; use \
+             --dry-run to preview fixes instead",
+            output.display()
+        ));
+    }
+
+    if format.is_some() && dry_run {
+        return Err("--format is not supported together with --dry-run".to_string());
+    }
+
+    if is_dir {
+        if dry_run {
+            return Err("--dry-run is not supported together with a directory input".to_string());
+        }
+        if format.is_some() {
+            return Err("--format is not supported together with a directory input".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Render a `ValidationResult` in whichever of the supported output modes
+/// was requested: SARIF (`--format sarif`) takes precedence over `--json`,
+/// which in turn takes precedence over the human-readable default.
+fn print_validate_output(
+    result: &ffi::ValidationResult,
+    input_str: &str,
+    json: bool,
+    format: Option<&str>,
+) -> Result<()> {
+    if format == Some("sarif") {
+        println!("{}", serde_json::to_string_pretty(&sarif::to_sarif(result, input_str))?);
+    } else if json {
+        println!("{}", validation_result_to_json(result));
     } else {
-        let result = ffi::validate(&input_str)
-            .map_err(|e| anyhow::anyhow!("Validation failed: {}", e))?;
+        print_validation_result(result, input_str);
+    }
+
+    Ok(())
+}
+
+/// Resolve the rule configuration to use: start from a `--preset`, if any,
+/// then layer a `--config` file's overrides on top.
+fn resolve_rule_config(
+    preset: Option<&str>,
+    config: Option<&PathBuf>,
+) -> Result<config::RuleConfig> {
+    let mut resolved = match preset {
+        Some(name) => config::RuleConfig::preset(name).map_err(|e| anyhow::anyhow!(e))?,
+        None => config::RuleConfig::default(),
+    };
+
+    if let Some(path) = config {
+        let from_file = config::RuleConfig::from_file(path).map_err(|e| anyhow::anyhow!(e))?;
+        resolved = resolved.merged_with(from_file);
+    }
+
+    Ok(resolved)
+}
+
+/// Render a `ValidationResult` as JSON, using its own `Serialize` impl
+/// rather than round-tripping through `recad_core`'s `lc_validation_result_to_json`
+/// (needed anyway for the config/preset-driven path, where no
+/// `ValidationResult` comes straight from the C library).
+fn validation_result_to_json(result: &ffi::ValidationResult) -> String {
+    serde_json::to_string(result).expect("ValidationResult serialization is infallible")
+}
+
+/// Run the rule engine and list the non-conflicting subset of fixes it
+/// finds (`--dry-run`).
+///
+/// Writing those fixes back to disk (`--fix <output>`) remains blocked,
+/// not delivered — see the `fix` field doc comment on `Commands::Validate`
+/// for the backlog items (chunk0-3, chunk1-1) this is tracked against.
+/// It's blocked on `recad_core` gaining a per-entity mutation API (it only
+/// exposes whole-document open/save/validate/convert today); `--fix` is
+/// rejected up front in `validate_flag_combination` rather than landing
+/// here.
+fn cmd_validate_fix(info: &ffi::FileInfo) -> Result<ExitCode> {
+    let diagnostics = rules::run_rules(info);
+    let (fixes, report) = rules::select_fixes(&diagnostics);
+
+    println!("{}", "Auto-fix".cyan().bold());
+    println!("{}", "--------".cyan());
+    println!("  Rules fired:       {}", report.applied.len());
+    println!("  Entities changed:  {}", report.entities_changed);
+    println!("  Skipped conflicts: {}", report.skipped_conflicts.len());
+    println!();
+
+    let is_selected = |d: &rules::Diagnostic| {
+        d.fix
+            .as_ref()
+            .is_some_and(|fix| fixes.iter().any(|selected| std::ptr::eq(*selected, fix)))
+    };
+    for diagnostic in diagnostics.iter().filter(|d| is_selected(*d)) {
+        println!("  [{}] {}", diagnostic.rule, diagnostic.message);
+    }
 
-        print_validation_result(&result, &input_str);
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Walk a directory of CAD files and emit one aggregated JSON document:
+/// a per-file `validate --json` result plus a rollup of totals and errors.
+///
+/// When `rule_config` is set (from `--config`/`--preset`), every file is run
+/// through `config::validate_with_config` instead of the raw C validator, so
+/// directory mode honors the same rule selection and severity overrides as
+/// single-file mode.
+///
+/// The process exit code is the same `--fail-on` bucketing `cmd_validate`
+/// applies to a single file, computed over the worst issue severity seen
+/// across every file in the batch (a file that failed to validate counts
+/// as an `error`), so this mode can drop directly into a build gate too.
+fn cmd_validate_batch(
+    root: &PathBuf,
+    recursive: bool,
+    jobs: usize,
+    fail_on: FailOn,
+    rule_config: Option<&config::RuleConfig>,
+) -> Result<ExitCode> {
+    let files = batch::discover_files(root, recursive);
+
+    let per_file: Vec<serde_json::Value> = batch::run_parallel(&files, jobs, |file| {
+        let path_str = file.path.to_string_lossy().to_string();
+        match rule_config {
+            Some(rule_config) => match config::validate_with_config(&path_str, rule_config) {
+                Ok(result) => serde_json::to_value(&result).unwrap_or_else(|_| {
+                    serde_json::json!({ "file": path_str, "kind": file.kind })
+                }),
+                Err(e) => serde_json::json!({ "file": path_str, "kind": file.kind, "error": e }),
+            },
+            None => match ffi::validate_json(&path_str) {
+                Ok(raw) => serde_json::from_str::<serde_json::Value>(&raw).unwrap_or_else(|_| {
+                    serde_json::json!({ "file": path_str, "kind": file.kind, "raw": raw })
+                }),
+                Err(e) => serde_json::json!({ "file": path_str, "kind": file.kind, "error": e }),
+            },
+        }
+    });
+
+    let mut invalid_count = 0i64;
+    let mut errors = Vec::new();
+    let mut worst_rank: Option<u8> = None;
+
+    for value in &per_file {
+        if let Some(err) = value.get("error").and_then(|e| e.as_str()) {
+            invalid_count += 1;
+            errors.push(serde_json::json!({
+                "file": value.get("file"),
+                "error": err,
+            }));
+            worst_rank = worst_rank.max(Some(severity_rank(LcSeverity::Error)));
+            continue;
+        }
+
+        if value.get("is_valid").and_then(|v| v.as_bool()) == Some(false) {
+            invalid_count += 1;
+        }
+
+        if let Some(issues) = value.get("issues").and_then(|i| i.as_array()) {
+            for issue in issues {
+                if let Some(severity) = issue.get("severity").and_then(|s| s.as_str()) {
+                    worst_rank = worst_rank.max(Some(severity_rank_str(severity)));
+                }
+            }
+        }
+    }
+
+    let report = serde_json::json!({
+        "files": per_file,
+        "summary": {
+            "total_files": files.len(),
+            "invalid_files": invalid_count,
+            "errors": errors,
+        },
+    });
+
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(fail_on_exit_code_for_rank(worst_rank, fail_on))
+}
+
+fn cmd_diff(old: &PathBuf, new: &PathBuf, json: bool) -> Result<()> {
+    let old_str = old.to_string_lossy();
+    let new_str = new.to_string_lossy();
+
+    let old_info = ffi::get_file_info(&old_str, LcDetailLevel::Full)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", old_str, e))?;
+    let new_info = ffi::get_file_info(&new_str, LcDetailLevel::Full)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", new_str, e))?;
+
+    let result = diff::compute_diff(&old_info, &new_info);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        let mut out = String::new();
+        diff::write_diff(&mut out, &result)?;
+        print!("{}", out);
     }
 
     Ok(())
@@ -320,3 +813,44 @@ fn print_validation_result(result: &ffi::ValidationResult, filename: &str) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_unknown_format() {
+        let err = validate_flag_combination(false, None, false, Some("json")).unwrap_err();
+        assert!(err.contains("Unknown --format"));
+    }
+
+    #[test]
+    fn rejects_fix_as_always_blocked() {
+        let path = PathBuf::from("out.dxf");
+        let err = validate_flag_combination(false, Some(&path), false, None).unwrap_err();
+        assert!(err.contains("is blocked"));
+    }
+
+    #[test]
+    fn rejects_format_with_dry_run() {
+        let err = validate_flag_combination(false, None, true, Some("sarif")).unwrap_err();
+        assert!(err.contains("--dry-run"));
+    }
+
+    #[test]
+    fn rejects_dry_run_with_a_directory_input() {
+        let err = validate_flag_combination(true, None, true, None).unwrap_err();
+        assert!(err.contains("directory input"));
+    }
+
+    #[test]
+    fn rejects_format_with_a_directory_input() {
+        let err = validate_flag_combination(true, None, false, Some("sarif")).unwrap_err();
+        assert!(err.contains("directory input"));
+    }
+
+    #[test]
+    fn accepts_a_plain_directory_validation() {
+        assert!(validate_flag_combination(true, None, false, None).is_ok());
+    }
+}
@@ -0,0 +1,236 @@
+//! Rule-based validation engine
+//!
+//! Each rule inspects an already-loaded `FileInfo` and emits zero or more
+//! diagnostics. Rules that know how to repair what they find also attach a
+//! `Fix`: a concrete, entity-scoped edit that `select_fixes` can select
+//! without conflicting with another rule's edit to the same entity.
+
+use crate::ffi::{EntityGeometry, FileInfo, LcSeverity};
+use std::collections::HashSet;
+
+/// A concrete, entity- or layer-scoped repair
+#[derive(Debug, Clone, serde::Serialize)]
+pub enum Fix {
+    /// Add a layer with the given name (with default properties)
+    AddLayer { name: String },
+    /// Clamp an entity's color index into the valid range
+    ClampColor { handle: i32, from: i32, to: i32 },
+    /// Remove an entity with no usable handle, identified by its position in
+    /// the file's entity list (a handle of 0 can't locate it the way the
+    /// other fixes do)
+    RemoveEntity { index: usize },
+    /// Flag an open LWPolyline as closed
+    ClosePolyline { handle: i32 },
+}
+
+impl Fix {
+    /// The key used to detect whether two fixes touch the same entity/layer
+    fn conflict_key(&self) -> String {
+        match self {
+            Fix::AddLayer { name } => format!("layer:{name}"),
+            Fix::ClampColor { handle, .. } => format!("entity:{handle}"),
+            Fix::RemoveEntity { index } => format!("entity_index:{index}"),
+            Fix::ClosePolyline { handle } => format!("entity:{handle}"),
+        }
+    }
+}
+
+/// A diagnostic raised by a validation rule
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Diagnostic {
+    pub rule: String,
+    pub severity: LcSeverity,
+    pub message: String,
+    pub location: String,
+    pub fix: Option<Fix>,
+}
+
+type Rule = fn(&FileInfo) -> Vec<Diagnostic>;
+
+/// Built-in rules, run in a fixed order so fix application is deterministic.
+pub const RULES: &[Rule] = &[
+    missing_layer,
+    invalid_color_index,
+    dangling_handle,
+    open_lwpolyline,
+];
+
+fn missing_layer(info: &FileInfo) -> Vec<Diagnostic> {
+    let known: HashSet<&str> = info.layers.iter().map(|l| l.name.as_str()).collect();
+    let mut reported = HashSet::new();
+    let mut diagnostics = Vec::new();
+
+    for entity in &info.entities {
+        if known.contains(entity.layer.as_str()) || !reported.insert(entity.layer.clone()) {
+            continue;
+        }
+        diagnostics.push(Diagnostic {
+            rule: "MISSING_LAYER".to_string(),
+            severity: LcSeverity::Error,
+            message: format!("entity references undefined layer `{}`", entity.layer),
+            location: format!("layer {}", entity.layer),
+            fix: Some(Fix::AddLayer {
+                name: entity.layer.clone(),
+            }),
+        });
+    }
+
+    diagnostics
+}
+
+fn invalid_color_index(info: &FileInfo) -> Vec<Diagnostic> {
+    info.entities
+        .iter()
+        .filter(|e| !(0..=256).contains(&e.color))
+        .map(|e| Diagnostic {
+            rule: "INVALID_COLOR_INDEX".to_string(),
+            severity: LcSeverity::Warning,
+            message: format!("color index {} is out of the valid 0-256 range", e.color),
+            location: format!("handle #{:X}", e.handle),
+            fix: Some(Fix::ClampColor {
+                handle: e.handle,
+                from: e.color,
+                to: e.color.clamp(0, 256),
+            }),
+        })
+        .collect()
+}
+
+/// DXF reserves handle `0` as invalid; an entity that never got a handle
+/// assigned can't be the target of a handle-based reference (block
+/// association, dimension, XDATA) elsewhere in the file, so it's reported
+/// and removed rather than repaired in place.
+fn dangling_handle(info: &FileInfo) -> Vec<Diagnostic> {
+    info.entities
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.handle == 0)
+        .map(|(index, e)| Diagnostic {
+            rule: "DANGLING_HANDLE".to_string(),
+            severity: LcSeverity::Error,
+            message: format!(
+                "{} entity has no handle assigned (handle 0 is reserved/invalid)",
+                e.entity_type.as_str()
+            ),
+            location: format!("entity #{index} on layer {}", e.layer),
+            fix: Some(Fix::RemoveEntity { index }),
+        })
+        .collect()
+}
+
+/// An LWPolyline whose `closed` flag is unset leaves its last segment open.
+fn open_lwpolyline(info: &FileInfo) -> Vec<Diagnostic> {
+    info.entities
+        .iter()
+        .filter(|e| matches!(e.geometry, EntityGeometry::LwPolyline { closed: false, .. }))
+        .map(|e| Diagnostic {
+            rule: "DXF_OPEN_POLYLINE".to_string(),
+            severity: LcSeverity::Warning,
+            message: "LWPolyline is not closed".to_string(),
+            location: format!("handle #{:X}", e.handle),
+            fix: Some(Fix::ClosePolyline { handle: e.handle }),
+        })
+        .collect()
+}
+
+/// Run every built-in rule against `info`, in a fixed order.
+pub fn run_rules(info: &FileInfo) -> Vec<Diagnostic> {
+    RULES.iter().flat_map(|rule| rule(info)).collect()
+}
+
+/// Report of selecting a non-conflicting subset of fixes: which rules fired,
+/// which were skipped due to a conflict, and how many entities were touched.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct FixReport {
+    pub applied: Vec<String>,
+    pub skipped_conflicts: Vec<String>,
+    pub entities_changed: usize,
+}
+
+/// Select the non-conflicting subset of fixes among `diagnostics`, walking
+/// them in order and skipping any fix whose conflict key was already
+/// claimed by an earlier one.
+pub fn select_fixes(diagnostics: &[Diagnostic]) -> (Vec<&Fix>, FixReport) {
+    let mut seen_keys = HashSet::new();
+    let mut selected = Vec::new();
+    let mut report = FixReport::default();
+
+    for diagnostic in diagnostics {
+        let Some(fix) = &diagnostic.fix else {
+            continue;
+        };
+
+        if seen_keys.insert(fix.conflict_key()) {
+            selected.push(fix);
+            report.applied.push(diagnostic.rule.clone());
+            if !matches!(fix, Fix::AddLayer { .. }) {
+                report.entities_changed += 1;
+            }
+        } else {
+            report.skipped_conflicts.push(diagnostic.rule.clone());
+        }
+    }
+
+    (selected, report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostic(rule: &str, fix: Fix) -> Diagnostic {
+        Diagnostic {
+            rule: rule.to_string(),
+            severity: LcSeverity::Warning,
+            message: String::new(),
+            location: String::new(),
+            fix: Some(fix),
+        }
+    }
+
+    #[test]
+    fn select_fixes_skips_a_later_fix_touching_the_same_entity() {
+        let diagnostics = vec![
+            diagnostic(
+                "INVALID_COLOR_INDEX",
+                Fix::ClampColor {
+                    handle: 1,
+                    from: 300,
+                    to: 256,
+                },
+            ),
+            diagnostic("DXF_OPEN_POLYLINE", Fix::ClosePolyline { handle: 1 }),
+        ];
+
+        let (selected, report) = select_fixes(&diagnostics);
+
+        assert_eq!(selected.len(), 1);
+        assert_eq!(report.applied, vec!["INVALID_COLOR_INDEX".to_string()]);
+        assert_eq!(
+            report.skipped_conflicts,
+            vec!["DXF_OPEN_POLYLINE".to_string()]
+        );
+        assert_eq!(report.entities_changed, 1);
+    }
+
+    #[test]
+    fn select_fixes_keeps_fixes_touching_distinct_entities() {
+        let diagnostics = vec![
+            diagnostic(
+                "INVALID_COLOR_INDEX",
+                Fix::ClampColor {
+                    handle: 1,
+                    from: 300,
+                    to: 256,
+                },
+            ),
+            diagnostic("DXF_OPEN_POLYLINE", Fix::ClosePolyline { handle: 2 }),
+        ];
+
+        let (selected, report) = select_fixes(&diagnostics);
+
+        assert_eq!(selected.len(), 2);
+        assert!(report.skipped_conflicts.is_empty());
+        assert_eq!(report.entities_changed, 2);
+    }
+}
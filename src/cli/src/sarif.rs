@@ -0,0 +1,141 @@
+//! SARIF 2.1.0 output for `validate`, so results can be consumed by generic
+//! diagnostic viewers and CI code-scanning annotations.
+
+use crate::ffi::{Issue, LcSeverity, ValidationResult};
+use serde_json::{json, Value};
+
+fn severity_to_level(severity: LcSeverity) -> &'static str {
+    match severity {
+        LcSeverity::Error => "error",
+        LcSeverity::Warning => "warning",
+        LcSeverity::Info => "note",
+    }
+}
+
+/// Turn an issue's free-form `location` string into a SARIF region when it
+/// encodes a line number (`"line N"`) or an entity handle (`"handle #HEX"`).
+fn location_region(location: &str) -> Option<Value> {
+    if let Some(line) = location.strip_prefix("line ").and_then(|s| s.trim().parse::<u64>().ok()) {
+        return Some(json!({ "startLine": line }));
+    }
+
+    if let Some(handle) = location.strip_prefix("handle #") {
+        return Some(json!({ "snippet": { "text": format!("handle #{handle}") } }));
+    }
+
+    None
+}
+
+fn rule_entry(issue: &Issue) -> Value {
+    json!({
+        "id": issue.code,
+        "defaultConfiguration": { "level": severity_to_level(issue.severity) },
+    })
+}
+
+fn result_entry(issue: &Issue, input_file: &str) -> Value {
+    let mut physical_location = json!({
+        "artifactLocation": { "uri": input_file },
+    });
+
+    if let Some(region) = location_region(&issue.location) {
+        physical_location["region"] = region;
+    }
+
+    json!({
+        "ruleId": issue.code,
+        "level": severity_to_level(issue.severity),
+        "message": { "text": issue.message },
+        "locations": [ { "physicalLocation": physical_location } ],
+    })
+}
+
+/// Serialize a `ValidationResult` as a single-run SARIF 2.1.0 log.
+pub fn to_sarif(result: &ValidationResult, input_file: &str) -> Value {
+    let mut rules: Vec<Value> = Vec::new();
+    let mut seen_codes = std::collections::HashSet::new();
+    for issue in &result.issues {
+        if seen_codes.insert(issue.code.clone()) {
+            rules.push(rule_entry(issue));
+        }
+    }
+
+    let results: Vec<Value> = result
+        .issues
+        .iter()
+        .map(|issue| result_entry(issue, input_file))
+        .collect();
+
+    json!({
+        "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [
+            {
+                "tool": {
+                    "driver": {
+                        "name": "cadutil",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules,
+                    },
+                },
+                "results": results,
+            },
+        ],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi::Issue;
+
+    #[test]
+    fn location_region_parses_a_line_location() {
+        assert_eq!(location_region("line 42"), Some(json!({ "startLine": 42 })));
+    }
+
+    #[test]
+    fn location_region_parses_a_handle_location() {
+        assert_eq!(
+            location_region("handle #1A"),
+            Some(json!({ "snippet": { "text": "handle #1A" } }))
+        );
+    }
+
+    #[test]
+    fn location_region_is_none_for_unrecognized_locations() {
+        assert_eq!(location_region("layer 0"), None);
+    }
+
+    #[test]
+    fn severity_to_level_maps_info_to_note() {
+        assert_eq!(severity_to_level(LcSeverity::Info), "note");
+    }
+
+    fn issue(code: &str, severity: LcSeverity, location: &str) -> Issue {
+        Issue {
+            code: code.to_string(),
+            severity,
+            message: String::new(),
+            location: location.to_string(),
+        }
+    }
+
+    #[test]
+    fn to_sarif_deduplicates_repeated_rule_ids() {
+        let result = ValidationResult {
+            is_valid: false,
+            issues: vec![
+                issue("MISSING_LAYER", LcSeverity::Error, "line 1"),
+                issue("MISSING_LAYER", LcSeverity::Error, "line 2"),
+            ],
+        };
+
+        let sarif = to_sarif(&result, "drawing.dxf");
+        let rules = sarif["runs"][0]["tool"]["driver"]["rules"].as_array().unwrap();
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+
+        assert_eq!(rules.len(), 1);
+        assert_eq!(results.len(), 2);
+    }
+}
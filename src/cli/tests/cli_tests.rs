@@ -400,100 +400,3 @@ mod error_handling_tests {
         assert!(!output.status.success(), "Convert without output file should fail");
     }
 }
-
-mod fixture_tests {
-    use super::*;
-
-    /// Get path to test fixtures directory
-    fn get_fixtures_path() -> PathBuf {
-        let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
-        PathBuf::from(manifest_dir).join("tests").join("test_fixtures")
-    }
-
-    #[test]
-    fn test_empty_dxf_info() {
-        let empty_file = get_fixtures_path().join("empty.dxf");
-        if !empty_file.exists() {
-            return; // Skip if fixture doesn't exist
-        }
-
-        let output = run_cadutil(&["info", empty_file.to_str().unwrap()]);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-
-        assert!(output.status.success(), "Info on empty DXF should succeed");
-        assert!(stdout.contains("Entities: 0") || stdout.contains("entity_count"),
-                "Should show zero entities");
-    }
-
-    #[test]
-    fn test_empty_dxf_validate() {
-        let empty_file = get_fixtures_path().join("empty.dxf");
-        if !empty_file.exists() {
-            return; // Skip if fixture doesn't exist
-        }
-
-        let output = run_cadutil(&["validate", empty_file.to_str().unwrap()]);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-
-        assert!(output.status.success(), "Validate on empty DXF should succeed");
-        // Should have warning about empty drawing
-        assert!(stdout.contains("EMPTY_DRAWING") || stdout.contains("no entities"),
-                "Should warn about empty drawing");
-    }
-
-    #[test]
-    fn test_multi_layer_dxf_info() {
-        let multi_layer_file = get_fixtures_path().join("multi_layer.dxf");
-        if !multi_layer_file.exists() {
-            return; // Skip if fixture doesn't exist
-        }
-
-        let output = run_cadutil(&["info", multi_layer_file.to_str().unwrap()]);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-
-        assert!(output.status.success(), "Info on multi-layer DXF should succeed");
-        // Should show multiple layers
-        assert!(stdout.contains("Walls") || stdout.contains("Layers"),
-                "Should show layer information");
-    }
-
-    #[test]
-    fn test_multi_layer_dxf_json() {
-        let multi_layer_file = get_fixtures_path().join("multi_layer.dxf");
-        if !multi_layer_file.exists() {
-            return; // Skip if fixture doesn't exist
-        }
-
-        let output = run_cadutil(&["info", multi_layer_file.to_str().unwrap(), "--json"]);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-
-        assert!(output.status.success(), "JSON info on multi-layer DXF should succeed");
-
-        // Parse as JSON to validate structure
-        let json: serde_json::Value = serde_json::from_str(&stdout)
-            .expect("Output should be valid JSON");
-
-        assert!(json["layer_count"].as_i64().unwrap() >= 1, "Should have layers");
-        assert!(json["entity_count"].as_i64().unwrap() >= 1, "Should have entities");
-    }
-
-    #[test]
-    fn test_multi_layer_validate() {
-        let multi_layer_file = get_fixtures_path().join("multi_layer.dxf");
-        if !multi_layer_file.exists() {
-            return; // Skip if fixture doesn't exist
-        }
-
-        let output = run_cadutil(&["validate", multi_layer_file.to_str().unwrap(), "--json"]);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-
-        assert!(output.status.success(), "Validate on multi-layer DXF should succeed");
-
-        // Parse as JSON to validate structure
-        let json: serde_json::Value = serde_json::from_str(&stdout)
-            .expect("Output should be valid JSON");
-
-        assert!(json["is_valid"].as_bool().is_some(), "Should have is_valid field");
-        assert!(json["issues"].as_array().is_some(), "Should have issues array");
-    }
-}
@@ -0,0 +1,145 @@
+//! Data-driven golden-output regression tests
+//!
+//! Walks `tests/test_fixtures` for `.dxf` files and, for each one, runs
+//! `info --json` and `validate --json` against the built `cadutil` binary,
+//! comparing the output to a sibling `.expected.json` snapshot. Set
+//! `CADUTIL_BLESS=1` to regenerate the snapshots in place instead of
+//! asserting against them.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use libtest_mimic::{Arguments, Failed, Trial};
+
+fn fixtures_dir() -> PathBuf {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    PathBuf::from(manifest_dir)
+        .join("tests")
+        .join("test_fixtures")
+}
+
+fn binary_path() -> PathBuf {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    PathBuf::from(manifest_dir)
+        .join("target")
+        .join("debug")
+        .join("cadutil")
+}
+
+fn lib_path() -> String {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    PathBuf::from(manifest_dir)
+        .join("..")
+        .join("core")
+        .join("zig-out")
+        .join("lib")
+        .to_string_lossy()
+        .to_string()
+}
+
+fn run_cadutil(args: &[&str]) -> String {
+    let output = Command::new(binary_path())
+        .args(args)
+        .env("LD_LIBRARY_PATH", lib_path())
+        .output()
+        .expect("failed to execute cadutil");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// A minimal line-oriented unified diff, enough to point at the first
+/// divergence between an expected and actual snapshot.
+fn unified_diff(expected: &str, actual: &str) -> String {
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+    let mut out = String::new();
+
+    for i in 0..expected_lines.len().max(actual_lines.len()) {
+        let e = expected_lines.get(i).copied();
+        let a = actual_lines.get(i).copied();
+        if e != a {
+            if let Some(e) = e {
+                out.push_str(&format!("-{e}\n"));
+            }
+            if let Some(a) = a {
+                out.push_str(&format!("+{a}\n"));
+            }
+        }
+    }
+
+    out
+}
+
+fn is_blessing() -> bool {
+    env::var("CADUTIL_BLESS").map(|v| v == "1").unwrap_or(false)
+}
+
+fn check_snapshot(snapshot: &Path, actual: &str) -> Result<(), Failed> {
+    if is_blessing() {
+        fs::write(snapshot, actual)
+            .map_err(|e| format!("failed to write {}: {e}", snapshot.display()))?;
+        return Ok(());
+    }
+
+    let expected = fs::read_to_string(snapshot).map_err(|e| {
+        format!(
+            "missing snapshot {} ({e}); run with CADUTIL_BLESS=1 to create it",
+            snapshot.display()
+        )
+    })?;
+
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(format!(
+            "output does not match {}\n{}",
+            snapshot.display(),
+            unified_diff(&expected, actual)
+        )
+        .into())
+    }
+}
+
+fn info_trial(fixture: PathBuf) -> Trial {
+    let name = fixture.file_stem().unwrap().to_string_lossy().into_owned();
+    Trial::test(format!("info::{name}"), move || {
+        let actual = run_cadutil(&["info", fixture.to_str().unwrap(), "--json"]);
+        let snapshot = fixture.with_extension("info.expected.json");
+        check_snapshot(&snapshot, &actual)
+    })
+}
+
+fn validate_trial(fixture: PathBuf) -> Trial {
+    let name = fixture.file_stem().unwrap().to_string_lossy().into_owned();
+    Trial::test(format!("validate::{name}"), move || {
+        let actual = run_cadutil(&["validate", fixture.to_str().unwrap(), "--json"]);
+        let snapshot = fixture.with_extension("validate.expected.json");
+        check_snapshot(&snapshot, &actual)
+    })
+}
+
+fn discover_fixtures(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut fixtures: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("dxf"))
+        .collect();
+    fixtures.sort();
+    fixtures
+}
+
+fn main() {
+    let args = Arguments::from_args();
+
+    let trials = discover_fixtures(&fixtures_dir())
+        .into_iter()
+        .flat_map(|fixture| [info_trial(fixture.clone()), validate_trial(fixture)])
+        .collect();
+
+    libtest_mimic::run(&args, trials).exit();
+}